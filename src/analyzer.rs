@@ -1,8 +1,9 @@
 use crate::{Project, Task};
-use chrono::{Datelike, NaiveDate, NaiveDateTime, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
 use itertools::Itertools;
 use serde::Serialize;
 use std::cmp::Ordering;
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize)]
 pub struct AnalysisResult {
@@ -13,9 +14,154 @@ pub struct AnalysisResult {
     pub day: Vec<(String, TasksAnalysisResult)>,
     /// グループ別
     pub group: Vec<(String, TasksAnalysisResult)>,
+    /// 週別（`week_start` を起点とした暦週。稼働のない週も0埋めで含む）
+    pub week: Vec<(NaiveDate, TasksAnalysisResult)>,
+    /// グループ別の周期検出と残作業量の予測（`forecast_periods` 指定時のみ）
+    pub forecast: Vec<(String, ForecastResult)>,
 }
 
-pub fn analyze(tasks: Vec<Task>, project_id: &str, value: Option<i64>) -> Option<AnalysisResult> {
+/// 検出された繰り返し周期
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecurrencePeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+/// グループごとの周期検出結果と残作業量の予測
+#[derive(Debug, Serialize)]
+pub struct ForecastResult {
+    /// 検出された周期
+    pub period: RecurrencePeriod,
+    /// 連続する稼働日の間隔のうち、検出周期に一致した割合
+    pub confidence: f64,
+    /// 予測対象の周期数
+    pub periods_forecast: i64,
+    /// `mean(timespan) * periods_forecast` による予測作業量（分）
+    pub forecast_minutes: f64,
+}
+
+/// 前回の稼働日からの間隔（日数）を最も近い周期に分類する
+fn classify_gap(days: i64) -> RecurrencePeriod {
+    const BUCKETS: [(RecurrencePeriod, f64); 3] = [
+        (RecurrencePeriod::Daily, 1.0),
+        (RecurrencePeriod::Weekly, 7.0),
+        (RecurrencePeriod::Monthly, 30.0),
+    ];
+    BUCKETS
+        .iter()
+        .min_by(|(_, a), (_, b)| {
+            (days as f64 - a)
+                .abs()
+                .partial_cmp(&(days as f64 - b).abs())
+                .unwrap()
+        })
+        .map(|(period, _)| *period)
+        .unwrap()
+}
+
+/// 稼働日の間隔をクラスタリングし、最も多く現れた周期とその一致率を返す
+fn detect_recurrence(dates: &[NaiveDate]) -> Option<(RecurrencePeriod, f64)> {
+    if dates.len() < 2 {
+        return None;
+    }
+    let classified: Vec<RecurrencePeriod> = dates
+        .windows(2)
+        .map(|w| classify_gap((w[1] - w[0]).num_days()))
+        .collect();
+
+    let mut counts: Vec<(RecurrencePeriod, usize)> = Vec::new();
+    for period in &classified {
+        match counts.iter_mut().find(|(p, _)| p == period) {
+            Some((_, c)) => *c += 1,
+            None => counts.push((*period, 1)),
+        }
+    }
+    let (dominant, count) = counts.into_iter().max_by_key(|(_, c)| *c)?;
+    Some((dominant, count as f64 / classified.len() as f64))
+}
+
+/// グループの稼働履歴から周期を検出し、`periods_forecast` 周期分の残作業量を見積もる
+fn forecast_group(tasks: &Tasks, periods_forecast: i64) -> Option<ForecastResult> {
+    let dates = tasks.begin_dates();
+    let (period, confidence) = detect_recurrence(&dates)?;
+    let mean_timespan =
+        tasks.0.iter().map(|t| t.timespan).sum::<i64>() as f64 / tasks.0.len() as f64;
+
+    Some(ForecastResult {
+        period,
+        confidence,
+        periods_forecast,
+        forecast_minutes: mean_timespan * periods_forecast as f64,
+    })
+}
+
+/// `date` が属する週の開始日を求める（週の開始曜日は `week_start` で指定する）
+fn week_start_of(date: NaiveDate, week_start: Weekday) -> NaiveDate {
+    let days_since_start =
+        (date.weekday().num_days_from_monday() + 7 - week_start.num_days_from_monday()) % 7;
+    date - Duration::days(days_since_start as i64)
+}
+
+/// 最初の週から最後の週までの間にある稼働のない週を、0埋めの `TasksAnalysisResult` で埋める
+/// （`weekly_goal_minutes` 指定時は、0埋めの週の `goal_ratio` は `0.0` になる）
+fn fill_weeks(
+    weeks: Vec<(NaiveDate, TasksAnalysisResult)>,
+    weekly_goal_minutes: Option<i64>,
+) -> Vec<(NaiveDate, TasksAnalysisResult)> {
+    let Some(first) = weeks.iter().map(|(d, _)| *d).min() else {
+        return weeks;
+    };
+    let last = weeks.iter().map(|(d, _)| *d).max().unwrap();
+    let mut by_week: HashMap<NaiveDate, TasksAnalysisResult> = weeks.into_iter().collect();
+
+    let mut filled = Vec::new();
+    let mut cursor = first;
+    while cursor <= last {
+        let result = by_week.remove(&cursor).unwrap_or_else(|| TasksAnalysisResult {
+            goal_ratio: weekly_goal_minutes.map(|_| 0.0),
+            ..TasksAnalysisResult::zero()
+        });
+        filled.push((cursor, result));
+        cursor += Duration::days(7);
+    }
+    filled
+}
+
+/// `analyze` の挙動を制御するオプション群
+#[derive(Debug, Clone, Copy)]
+pub struct AnalyzeOptions {
+    /// チャート1ブロックあたりの分数
+    pub block_minutes: i64,
+    /// 週集計の開始曜日
+    pub week_start: Weekday,
+    /// 日次目標（分）。指定時のみ `daily_goal`/`days_on_target`/`days_missed` が埋まる
+    pub daily_goal_minutes: Option<i64>,
+    /// 週次目標（分）。指定時のみ週別結果の `goal_ratio` が埋まる
+    pub weekly_goal_minutes: Option<i64>,
+    /// 予測対象の周期数。指定時のみ `forecast` が埋まる
+    pub forecast_periods: Option<i64>,
+}
+
+pub fn analyze(
+    tasks: Vec<Task>,
+    project_id: &str,
+    value: Option<i64>,
+    options: AnalyzeOptions,
+) -> Option<AnalysisResult> {
+    let AnalyzeOptions {
+        block_minutes,
+        week_start,
+        daily_goal_minutes,
+        weekly_goal_minutes,
+        forecast_periods,
+    } = options;
+
+    if block_minutes <= 0 {
+        return None;
+    }
+
     let target_tasks = Tasks(
         tasks
             .into_iter()
@@ -33,8 +179,30 @@ pub fn analyze(tasks: Vec<Task>, project_id: &str, value: Option<i64>) -> Option
     );
     let project_name = target_tasks.project_name(project_id)?;
 
-    fn analyze_group(g: Vec<(String, Tasks)>) -> Vec<(String, TasksAnalysisResult)> {
-        g.into_iter().map(|(k, v)| (k, v.analyze())).collect()
+    fn analyze_group(
+        g: Vec<(String, Tasks)>,
+        block_minutes: i64,
+        daily_goal_minutes: Option<i64>,
+    ) -> Vec<(String, TasksAnalysisResult)> {
+        g.into_iter()
+            .map(|(k, v)| (k, v.analyze(block_minutes, daily_goal_minutes, None)))
+            .collect()
+    }
+
+    fn analyze_week_group(
+        g: Vec<(NaiveDate, Tasks)>,
+        block_minutes: i64,
+        daily_goal_minutes: Option<i64>,
+        weekly_goal_minutes: Option<i64>,
+    ) -> Vec<(NaiveDate, TasksAnalysisResult)> {
+        g.into_iter()
+            .map(|(k, v)| {
+                (
+                    k,
+                    v.analyze(block_minutes, daily_goal_minutes, weekly_goal_minutes),
+                )
+            })
+            .collect()
     }
 
     let day = target_tasks.group_by(|t| match t.begin_time.weekday() {
@@ -48,13 +216,27 @@ pub fn analyze(tasks: Vec<Task>, project_id: &str, value: Option<i64>) -> Option
         }
     });
     let group = target_tasks.group_by(|t| t.group.clone().unwrap_or("-".into()));
+    let week = target_tasks.group_by_date(|t| week_start_of(t.begin_time.date(), week_start));
+    // `group` を消費する `analyze_group` に渡す前に、同じグルーピング結果を予測にも使い回す
+    let forecast = match forecast_periods {
+        Some(periods) => group
+            .iter()
+            .filter_map(|(k, v)| forecast_group(v, periods).map(|f| (k.clone(), f)))
+            .collect(),
+        None => Vec::new(),
+    };
 
     Some(AnalysisResult {
         project_name,
         value,
-        all: target_tasks.analyze(),
-        day: analyze_group(day),
-        group: analyze_group(group),
+        all: target_tasks.analyze(block_minutes, daily_goal_minutes, None),
+        day: analyze_group(day, block_minutes, daily_goal_minutes),
+        group: analyze_group(group, block_minutes, daily_goal_minutes),
+        week: fill_weeks(
+            analyze_week_group(week, block_minutes, daily_goal_minutes, weekly_goal_minutes),
+            weekly_goal_minutes,
+        ),
+        forecast,
     })
 }
 
@@ -127,6 +309,157 @@ impl From<Task> for AnalysisResultTask {
 #[derive(Debug, Serialize)]
 struct Tasks(Vec<AnalysisResultTask>, Option<i64>);
 
+/// 1日分の作業時間ブロックグラフ
+#[derive(Debug, Serialize, Clone)]
+pub struct DayChartRow {
+    pub date: NaiveDate,
+    pub blocks: String,
+}
+
+/// 日次目標に対する達成状況
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalStatus {
+    Met,
+    Under,
+    Over,
+}
+
+/// 日次目標に対する日別の達成状況
+#[derive(Debug, Serialize, Clone)]
+pub struct DayGoalRow {
+    pub date: NaiveDate,
+    pub work_time: i64,
+    pub status: GoalStatus,
+}
+
+/// 日別作業時間（分）に対する統計指標。新しい指標はこの trait を実装して
+/// `day_metric_registry` に追加するだけで `analyze` の結果に反映される
+trait DayMetric {
+    fn name(&self) -> &'static str;
+    fn compute(&self, values: &[i64]) -> f64;
+}
+
+struct MeanMetric;
+
+impl DayMetric for MeanMetric {
+    fn name(&self) -> &'static str {
+        "mean"
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        values.iter().sum::<i64>() as f64 / values.len() as f64
+    }
+}
+
+struct MinMetric;
+
+impl DayMetric for MinMetric {
+    fn name(&self) -> &'static str {
+        "min"
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        values.iter().min().copied().unwrap_or(0) as f64
+    }
+}
+
+struct MaxMetric;
+
+impl DayMetric for MaxMetric {
+    fn name(&self) -> &'static str {
+        "max"
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        values.iter().max().copied().unwrap_or(0) as f64
+    }
+}
+
+struct MedianMetric;
+
+impl DayMetric for MedianMetric {
+    fn name(&self) -> &'static str {
+        "median"
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        let sorted = values.iter().copied().sorted().collect::<Vec<_>>();
+        let mid = sorted.len() / 2;
+        if sorted.len() % 2 == 0 {
+            (sorted[mid - 1] + sorted[mid]) as f64 / 2.0
+        } else {
+            sorted[mid] as f64
+        }
+    }
+}
+
+struct StdevMetric;
+
+impl DayMetric for StdevMetric {
+    fn name(&self) -> &'static str {
+        "stdev"
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        let mean = MeanMetric.compute(values);
+        (values
+            .iter()
+            .map(|v| (*v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / values.len() as f64)
+            .sqrt()
+    }
+}
+
+/// 線形補間なしの最近傍法によるパーセンタイル
+struct PercentileMetric {
+    name: &'static str,
+    percentile: f64,
+}
+
+impl DayMetric for PercentileMetric {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn compute(&self, values: &[i64]) -> f64 {
+        if values.is_empty() {
+            return 0.0;
+        }
+        let sorted = values.iter().copied().sorted().collect::<Vec<_>>();
+        let rank = (self.percentile * (sorted.len() - 1) as f64).round() as usize;
+        sorted[rank.min(sorted.len() - 1)] as f64
+    }
+}
+
+fn day_metric_registry() -> Vec<Box<dyn DayMetric>> {
+    vec![
+        Box::new(MeanMetric),
+        Box::new(MinMetric),
+        Box::new(MaxMetric),
+        Box::new(MedianMetric),
+        Box::new(StdevMetric),
+        Box::new(PercentileMetric {
+            name: "p90",
+            percentile: 0.9,
+        }),
+        Box::new(PercentileMetric {
+            name: "p95",
+            percentile: 0.95,
+        }),
+    ]
+}
+
 #[derive(Debug, Serialize)]
 pub struct TasksAnalysisResult {
     /// 合計見積時間
@@ -137,18 +470,20 @@ pub struct TasksAnalysisResult {
     pub total_time_gap_ratio: Option<f64>,
     /// 稼働日数（1分でも稼働したらその日は稼働したとしてカウント）
     pub work_days: i64,
-    /// 1日あたり作業時間平均
-    pub work_time_per_day: f64,
-    /// 1日あたり作業時間最大
-    pub work_time_per_day_max: i64,
-    /// 1日あたり作業時間最小
-    pub work_time_per_day_min: i64,
-    /// 1日あたり作業時間中央
-    pub work_time_per_day_median: i64,
-    /// 1日あたり作業時間標準偏差
-    pub work_time_per_day_deviation: f64,
+    /// 1日あたり作業時間の統計指標（mean/min/max/median/stdev/p90/p95）
+    pub day_metrics: Vec<(String, f64)>,
     /// 1ページあたりの作業時間（ページ数といったパラメータを外から差し込む）
     pub work_time_per_value: Option<f64>,
+    /// 日別の作業時間ブロックグラフ（未稼働日も `0` ブロックで埋める）
+    pub chart: Vec<DayChartRow>,
+    /// 日次目標（`daily_goal_minutes` 指定時のみ）に対する日別の達成状況
+    pub daily_goal: Option<Vec<DayGoalRow>>,
+    /// 日次目標を達成した日数
+    pub days_on_target: Option<i64>,
+    /// 日次目標を達成できなかった日数
+    pub days_missed: Option<i64>,
+    /// 週次目標に対する達成率（`total_work_time / weekly_goal_minutes`。週別集計時のみ）
+    pub goal_ratio: Option<f64>,
     /// 作業別（タスクごとの所要時間を並べる）
     pub tasks: Vec<AnalysisResultTask>,
 }
@@ -164,6 +499,19 @@ impl Tasks {
             .collect()
     }
 
+    fn group_by_date<F: Fn(&&AnalysisResultTask) -> NaiveDate>(
+        &self,
+        key: F,
+    ) -> Vec<(NaiveDate, Self)> {
+        self.0
+            .iter()
+            .sorted_by_key(|a| key(a))
+            .group_by::<NaiveDate, _>(|a| key(a))
+            .into_iter()
+            .map(|(k, v)| (k, Self(v.cloned().collect(), self.1)))
+            .collect()
+    }
+
     fn project_name(&self, project_id: &str) -> Option<String> {
         self.0
             .iter()
@@ -196,47 +544,27 @@ impl Tasks {
             .count() as i64
     }
 
-    fn work_time_per_day(&self) -> f64 {
-        self.total_work_time() as f64 / self.work_days() as f64
-    }
-
-    fn work_time_per_day_max(&self) -> i64 {
-        self.work_time_per_days()
-            .iter()
-            .map(|(_, v)| v)
-            .max()
-            .map(|v| *v)
-            .unwrap_or(0)
-    }
-
-    fn work_time_per_day_min(&self) -> i64 {
-        self.work_time_per_days()
-            .iter()
-            .map(|(_, v)| v)
-            .min()
-            .map(|v| *v)
-            .unwrap_or(0)
-    }
-
-    fn work_time_per_day_median(&self) -> i64 {
-        let v: Vec<i64> = self
-            .work_time_per_days()
+    /// 稼働した日付を重複なく時系列順に並べたもの
+    fn begin_dates(&self) -> Vec<NaiveDate> {
+        self.0
             .iter()
-            .map(|(_, v)| *v)
+            .map(|t| t.begin_time.date())
+            .unique()
             .sorted()
-            .collect();
-        v.get(v.len() / 2).map(|v| *v).unwrap_or(0)
+            .collect()
     }
 
-    fn work_time_per_day_deviation(&self) -> f64 {
-        let a = self.work_time_per_day();
-        (self
+    /// 登録されている `DayMetric` を日別作業時間（分）に適用した結果
+    fn day_metrics(&self) -> Vec<(String, f64)> {
+        let values: Vec<i64> = self
             .work_time_per_days()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect();
+        day_metric_registry()
             .iter()
-            .map(|(_, v)| (*v as f64 - a).powi(2))
-            .sum::<f64>()
-            / self.0.len() as f64)
-            .sqrt()
+            .map(|m| (m.name().to_string(), m.compute(&values)))
+            .collect()
     }
 
     fn work_time_per_value(&self) -> Option<f64> {
@@ -258,9 +586,95 @@ impl Tasks {
             .collect()
     }
 
-    fn analyze(self) -> TasksAnalysisResult {
+    /// `work_time_per_days` を元に、最初の稼働日から最後の稼働日までの未稼働日を `0` 分で埋める
+    fn work_time_per_days_filled(&self) -> Vec<(NaiveDate, i64)> {
+        let per_day = self.work_time_per_days();
+        let Some(first) = per_day.iter().map(|(d, _)| *d).min() else {
+            return Vec::new();
+        };
+        let last = per_day.iter().map(|(d, _)| *d).max().unwrap();
+
+        first
+            .iter_days()
+            .take_while(|d| *d <= last)
+            .map(|date| {
+                let minutes = per_day
+                    .iter()
+                    .find(|(d, _)| *d == date)
+                    .map(|(_, m)| *m)
+                    .unwrap_or(0);
+                (date, minutes)
+            })
+            .collect()
+    }
+
+    /// `work_time_per_days_filled` を元に、未稼働日も `0` ブロックで埋めたブロックグラフを作る
+    ///
+    /// `block_minutes` が0以下の場合は（`analyze` 側で弾かれるはずだが）割り算を避けて空を返す
+    fn work_time_chart(&self, block_minutes: i64) -> Vec<(NaiveDate, String)> {
+        if block_minutes <= 0 {
+            return Vec::new();
+        }
+
+        self.work_time_per_days_filled()
+            .into_iter()
+            .map(|(date, minutes)| {
+                let hour_blocks = minutes / block_minutes;
+                (date, "█".repeat(hour_blocks.max(0) as usize))
+            })
+            .collect()
+    }
+
+    /// `work_time_per_days_filled` を元に、未稼働日（作業時間0分）も含めた日別の目標達成状況（`met`/`under`/`over`）を作る
+    fn daily_goal_rows(&self, daily_goal_minutes: Option<i64>) -> Option<Vec<DayGoalRow>> {
+        let goal = daily_goal_minutes?;
+        Some(
+            self.work_time_per_days_filled()
+                .into_iter()
+                .map(|(date, work_time)| {
+                    let status = match work_time.cmp(&goal) {
+                        Ordering::Equal => GoalStatus::Met,
+                        Ordering::Less => GoalStatus::Under,
+                        Ordering::Greater => GoalStatus::Over,
+                    };
+                    DayGoalRow {
+                        date,
+                        work_time,
+                        status,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// `weekly_goal_minutes` に対する達成率
+    fn goal_ratio(&self, weekly_goal_minutes: Option<i64>) -> Option<f64> {
+        let goal = weekly_goal_minutes?;
+        Some(self.total_work_time() as f64 / goal as f64)
+    }
+
+    fn analyze(
+        self,
+        block_minutes: i64,
+        daily_goal_minutes: Option<i64>,
+        weekly_goal_minutes: Option<i64>,
+    ) -> TasksAnalysisResult {
         let tw = self.total_work_time();
         let te = self.total_estimated_time();
+        let chart = self
+            .work_time_chart(block_minutes)
+            .into_iter()
+            .map(|(date, blocks)| DayChartRow { date, blocks })
+            .collect();
+        let daily_goal = self.daily_goal_rows(daily_goal_minutes);
+        let (days_on_target, days_missed) = match &daily_goal {
+            Some(rows) => {
+                let on_target = rows.iter().filter(|r| r.status == GoalStatus::Met).count() as i64;
+                (Some(on_target), Some(rows.len() as i64 - on_target))
+            }
+            None => (None, None),
+        };
+        let goal_ratio = self.goal_ratio(weekly_goal_minutes);
 
         TasksAnalysisResult {
             total_estimated_time: te,
@@ -271,13 +685,156 @@ impl Tasks {
                 Some(tw as f64 / te as f64)
             },
             work_days: self.work_days(),
-            work_time_per_day: self.work_time_per_day(),
-            work_time_per_day_max: self.work_time_per_day_max(),
-            work_time_per_day_min: self.work_time_per_day_min(),
-            work_time_per_day_median: self.work_time_per_day_median(),
-            work_time_per_day_deviation: self.work_time_per_day_deviation(),
+            day_metrics: self.day_metrics(),
             work_time_per_value: self.work_time_per_value(),
+            chart,
+            daily_goal,
+            days_on_target,
+            days_missed,
+            goal_ratio,
             tasks: self.tasks(),
         }
     }
 }
+
+impl TasksAnalysisResult {
+    /// 稼働のない週を埋めるための0埋め結果
+    fn zero() -> Self {
+        Self {
+            total_estimated_time: 0,
+            total_work_time: 0,
+            total_time_gap_ratio: None,
+            work_days: 0,
+            day_metrics: day_metric_registry()
+                .iter()
+                .map(|m| (m.name().to_string(), m.compute(&[])))
+                .collect(),
+            work_time_per_value: None,
+            chart: Vec::new(),
+            daily_goal: None,
+            days_on_target: None,
+            days_missed: None,
+            goal_ratio: None,
+            tasks: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_on(date: (i32, u32, u32), timespan_minutes: i64) -> AnalysisResultTask {
+        let begin_time = NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let end_time = begin_time + Duration::minutes(timespan_minutes);
+        AnalysisResultTask {
+            id: format!("{begin_time}"),
+            name: "task".into(),
+            group: None,
+            project: None,
+            comment: None,
+            estimated_time: None,
+            time_gap_ratio: None,
+            begin_time,
+            end_time,
+            timespan: timespan_minutes,
+            holiday: false,
+        }
+    }
+
+    #[test]
+    fn median_averages_the_two_middle_values_for_even_length_samples() {
+        // 4 distinct work days -> no single middle element, must average the two centre values.
+        let tasks = Tasks(
+            vec![
+                task_on((2026, 1, 1), 10),
+                task_on((2026, 1, 2), 20),
+                task_on((2026, 1, 3), 30),
+                task_on((2026, 1, 4), 40),
+            ],
+            None,
+        );
+
+        let median = tasks
+            .day_metrics()
+            .into_iter()
+            .find(|(name, _)| name == "median")
+            .map(|(_, v)| v)
+            .unwrap();
+
+        assert_eq!(median, 25.0);
+    }
+
+    #[test]
+    fn deviation_divides_by_distinct_work_days_not_task_count() {
+        // 3 tasks but only 2 distinct work days (40min and 20min) -> stdev must use 2 as the
+        // sample size, not 3.
+        let tasks = Tasks(
+            vec![
+                task_on((2026, 1, 1), 10),
+                task_on((2026, 1, 1), 30),
+                task_on((2026, 1, 2), 20),
+            ],
+            None,
+        );
+
+        let stdev = tasks
+            .day_metrics()
+            .into_iter()
+            .find(|(name, _)| name == "stdev")
+            .map(|(_, v)| v)
+            .unwrap();
+
+        assert_eq!(stdev, 10.0);
+    }
+
+    #[test]
+    fn work_time_chart_fills_idle_days_between_active_days() {
+        // Active on Jan 1 and Jan 4 only -> Jan 2/3 must still appear as idle (0 block) days.
+        let tasks = Tasks(
+            vec![task_on((2026, 1, 1), 30), task_on((2026, 1, 4), 20)],
+            None,
+        );
+
+        let chart = tasks.work_time_chart(10);
+        let dates: Vec<NaiveDate> = chart.iter().map(|(d, _)| *d).collect();
+
+        assert_eq!(
+            dates,
+            vec![
+                NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 3).unwrap(),
+                NaiveDate::from_ymd_opt(2026, 1, 4).unwrap(),
+            ]
+        );
+        assert_eq!(chart[0].1, "█".repeat(3));
+        assert_eq!(chart[1].1, "");
+        assert_eq!(chart[2].1, "");
+        assert_eq!(chart[3].1, "█".repeat(2));
+    }
+
+    #[test]
+    fn work_time_chart_does_not_panic_on_non_positive_block_minutes() {
+        let tasks = Tasks(vec![task_on((2026, 1, 1), 30)], None);
+
+        assert_eq!(tasks.work_time_chart(0), Vec::new());
+        assert_eq!(tasks.work_time_chart(-5), Vec::new());
+    }
+
+    #[test]
+    fn analyze_rejects_non_positive_block_minutes() {
+        let options = AnalyzeOptions {
+            block_minutes: 0,
+            week_start: Weekday::Mon,
+            daily_goal_minutes: None,
+            weekly_goal_minutes: None,
+            forecast_periods: None,
+        };
+
+        assert!(analyze(Vec::new(), "project", None, options).is_none());
+    }
+}
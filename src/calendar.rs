@@ -0,0 +1,185 @@
+use crate::analyzer::AnalysisResultTask;
+use chrono::{NaiveDate, NaiveDateTime};
+use itertools::Itertools;
+use std::fmt::Write as _;
+
+const PUBLIC_LABEL: &str = "busy";
+
+/// カレンダー出力時の公開範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalendarPrivacy {
+    /// タスク名・コメントをそのまま表示する
+    Private,
+    /// タスク名・コメントを隠し、代わりに `busy` とだけ表示する
+    Public,
+}
+
+fn display_name(task: &AnalysisResultTask, privacy: CalendarPrivacy) -> String {
+    match privacy {
+        CalendarPrivacy::Private => task.name.clone(),
+        CalendarPrivacy::Public => PUBLIC_LABEL.into(),
+    }
+}
+
+fn display_comment(task: &AnalysisResultTask, privacy: CalendarPrivacy) -> Option<String> {
+    match privacy {
+        CalendarPrivacy::Private => task.comment.clone(),
+        CalendarPrivacy::Public => None,
+    }
+}
+
+/// `begin_time` の日付ごとにタスクをまとめる
+fn group_by_day(tasks: &[AnalysisResultTask]) -> Vec<(NaiveDate, Vec<&AnalysisResultTask>)> {
+    tasks
+        .iter()
+        .sorted_by_key(|t| t.begin_time)
+        .group_by(|t| t.begin_time.date())
+        .into_iter()
+        .map(|(date, ts)| (date, ts.collect()))
+        .collect()
+}
+
+/// ソート済みの `AnalysisResultTask` を日付ごとのHTMLテーブルに変換する
+pub fn tasks_to_html(tasks: &[AnalysisResultTask], privacy: CalendarPrivacy) -> String {
+    let mut html = String::from("<table>\n");
+    for (date, day_tasks) in group_by_day(tasks) {
+        writeln!(
+            html,
+            "  <tr><th colspan=\"3\">{}</th></tr>",
+            date.format("%Y-%m-%d")
+        )
+        .unwrap();
+        for task in day_tasks {
+            let name = html_escape(&display_name(task, privacy));
+            let comment = display_comment(task, privacy)
+                .map(|c| html_escape(&c))
+                .unwrap_or_default();
+            html.push_str("  <tr>\n");
+            writeln!(
+                html,
+                "    <td>{}–{} ({}min)</td>",
+                task.begin_time.format("%H:%M"),
+                task.end_time.format("%H:%M"),
+                task.timespan
+            )
+            .unwrap();
+            writeln!(html, "    <td>{name}</td>").unwrap();
+            writeln!(html, "    <td>{comment}</td>").unwrap();
+            html.push_str("  </tr>\n");
+        }
+    }
+    html.push_str("</table>\n");
+    html
+}
+
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// ソート済みの `AnalysisResultTask` をiCalendar（.ics）のVEVENTストリームに変換する
+///
+/// `generated_at` はUTCでの生成時刻で、各VEVENTのRFC 5545必須プロパティ `DTSTAMP` に使われる
+pub fn tasks_to_ical(
+    tasks: &[AnalysisResultTask],
+    privacy: CalendarPrivacy,
+    generated_at: NaiveDateTime,
+) -> String {
+    let mut ics = String::from(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//taskchutecloud-cli//analyzer//EN\r\n",
+    );
+    for task in tasks.iter().sorted_by_key(|t| t.begin_time) {
+        let summary = ical_escape(&display_name(task, privacy));
+        ics.push_str("BEGIN:VEVENT\r\n");
+        write!(ics, "UID:{}@taskchutecloud-cli\r\n", ical_escape(&task.id)).unwrap();
+        write!(ics, "DTSTAMP:{}\r\n", format_ical_utc_time(generated_at)).unwrap();
+        write!(ics, "DTSTART:{}\r\n", format_ical_time(task.begin_time)).unwrap();
+        write!(ics, "DTEND:{}\r\n", format_ical_time(task.end_time)).unwrap();
+        write!(ics, "SUMMARY:{summary}\r\n").unwrap();
+        if let Some(comment) = display_comment(task, privacy) {
+            write!(ics, "DESCRIPTION:{}\r\n", ical_escape(&comment)).unwrap();
+        }
+        ics.push_str("END:VEVENT\r\n");
+    }
+    ics.push_str("END:VCALENDAR\r\n");
+    ics
+}
+
+fn format_ical_time(t: NaiveDateTime) -> String {
+    t.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn format_ical_utc_time(t: NaiveDateTime) -> String {
+    t.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn ical_escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET_NAME: &str = "Confidential project kickoff";
+    const SECRET_COMMENT: &str = "discussed layoffs";
+
+    fn secret_task() -> AnalysisResultTask {
+        let begin_time = NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        AnalysisResultTask {
+            id: "task-1".into(),
+            name: SECRET_NAME.into(),
+            group: None,
+            project: None,
+            comment: Some(SECRET_COMMENT.into()),
+            estimated_time: None,
+            time_gap_ratio: None,
+            begin_time,
+            end_time: begin_time + chrono::Duration::minutes(30),
+            timespan: 30,
+            holiday: false,
+        }
+    }
+
+    #[test]
+    fn public_html_never_leaks_name_or_comment() {
+        let html = tasks_to_html(&[secret_task()], CalendarPrivacy::Public);
+        assert!(!html.contains(SECRET_NAME));
+        assert!(!html.contains(SECRET_COMMENT));
+        assert!(html.contains(PUBLIC_LABEL));
+    }
+
+    #[test]
+    fn public_ical_never_leaks_name_or_comment() {
+        let generated_at = NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let ics = tasks_to_ical(&[secret_task()], CalendarPrivacy::Public, generated_at);
+        assert!(!ics.contains(SECRET_NAME));
+        assert!(!ics.contains(SECRET_COMMENT));
+        assert!(ics.contains(PUBLIC_LABEL));
+    }
+
+    #[test]
+    fn private_html_and_ical_include_name_and_comment() {
+        let html = tasks_to_html(&[secret_task()], CalendarPrivacy::Private);
+        assert!(html.contains(SECRET_NAME));
+        assert!(html.contains(SECRET_COMMENT));
+
+        let generated_at = NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let ics = tasks_to_ical(&[secret_task()], CalendarPrivacy::Private, generated_at);
+        assert!(ics.contains(SECRET_NAME));
+        assert!(ics.contains(SECRET_COMMENT));
+    }
+}